@@ -1,9 +1,12 @@
 use anyhow::Result;
 use aqueue::Actor;
-use clap::Parser;
-use std::fmt::{Display, Formatter};
-use std::io::{stdin, stdout, Write};
-use std::path::PathBuf;
+use clap::{Parser, ValueEnum};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::collections::BTreeMap;
+use std::io::{stderr, stdin, stdout, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::SystemTime;
 use tokio::sync::OnceCell;
 
@@ -36,6 +39,245 @@ struct Arg {
     /// Just collect the cleanable project dirs but don't attempt to clean anything
     #[clap(long = "dry-run")]
     dry_run: bool,
+    /// Move cleaned project directories to the OS trash/recycle bin instead of
+    /// permanently deleting them, so an accidental run can be undone
+    #[clap(long = "trash")]
+    trash: bool,
+    /// Log and skip individual removal failures instead of aborting the whole cleanup
+    #[clap(long = "force")]
+    force: bool,
+    /// Which build-artifact directory types to scan for, comma separated (default: all)
+    #[clap(long = "kinds", value_enum, value_delimiter = ',')]
+    kinds: Vec<ArtifactKind>,
+    /// Don't honor .gitignore/.ignore files while scanning
+    #[clap(long = "no-ignore")]
+    no_ignore: bool,
+    /// Additional glob pattern to exclude from scanning; may be passed multiple times
+    #[clap(long = "exclude", value_name = "GLOB")]
+    exclude: Vec<String>,
+    /// How to order the printed project lists
+    #[clap(long = "sort", value_enum, default_value = "size")]
+    sort: SortMode,
+    /// Reverse the sort order
+    #[clap(long = "reverse")]
+    reverse: bool,
+    /// Report live progress to stderr while scanning and cleaning up
+    #[clap(long = "progress")]
+    progress: bool,
+}
+
+/// A build-artifact directory type the scanner knows how to detect
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, ValueEnum)]
+enum ArtifactKind {
+    /// `target`, paired with a sibling `Cargo.toml`
+    Cargo,
+    /// `node_modules`, paired with a sibling `package.json`
+    Node,
+    /// `build`, language-agnostic build output
+    Build,
+    /// `dist`, language-agnostic distribution output
+    Dist,
+    /// `__pycache__`, Python bytecode cache
+    PyCache,
+    /// `.gradle`, paired with a sibling `build.gradle`
+    Gradle,
+}
+impl ArtifactKind {
+    const ALL: &'static [ArtifactKind] = &[
+        ArtifactKind::Cargo,
+        ArtifactKind::Node,
+        ArtifactKind::Build,
+        ArtifactKind::Dist,
+        ArtifactKind::PyCache,
+        ArtifactKind::Gradle,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            ArtifactKind::Cargo => "cargo",
+            ArtifactKind::Node => "node",
+            ArtifactKind::Build => "build",
+            ArtifactKind::Dist => "dist",
+            ArtifactKind::PyCache => "py-cache",
+            ArtifactKind::Gradle => "gradle",
+        }
+    }
+}
+
+/// A detection rule: a marker directory name, paired with the sibling manifest
+/// files that corroborate it (any one of them existing counts as a match). An
+/// empty list means the marker name alone is distinctive enough (e.g. `__pycache__`).
+struct ArtifactRule {
+    kind: ArtifactKind,
+    marker_dir: &'static str,
+    sibling_manifests: &'static [&'static str],
+}
+const ARTIFACT_RULES: &[ArtifactRule] = &[
+    ArtifactRule {
+        kind: ArtifactKind::Cargo,
+        marker_dir: "target",
+        sibling_manifests: &["Cargo.toml"],
+    },
+    ArtifactRule {
+        kind: ArtifactKind::Node,
+        marker_dir: "node_modules",
+        sibling_manifests: &["package.json"],
+    },
+    // `build` and `dist` are common names for hand-authored, version-controlled
+    // directories too (e.g. AOSP's top-level `build/`), so require a recognized
+    // source-tool manifest alongside them, same as every other rule here.
+    ArtifactRule {
+        kind: ArtifactKind::Build,
+        marker_dir: "build",
+        sibling_manifests: &[
+            "package.json",
+            "pom.xml",
+            "build.gradle",
+            "CMakeLists.txt",
+            "Makefile",
+            "setup.py",
+            "pyproject.toml",
+            "Cargo.toml",
+        ],
+    },
+    ArtifactRule {
+        kind: ArtifactKind::Dist,
+        marker_dir: "dist",
+        sibling_manifests: &["package.json", "setup.py", "pyproject.toml", "Cargo.toml"],
+    },
+    ArtifactRule {
+        kind: ArtifactKind::PyCache,
+        marker_dir: "__pycache__",
+        sibling_manifests: &[],
+    },
+    ArtifactRule {
+        kind: ArtifactKind::Gradle,
+        marker_dir: ".gradle",
+        sibling_manifests: &["build.gradle"],
+    },
+];
+
+/// Configuration threaded unchanged through the recursive directory walk
+struct ScanConfig {
+    kinds: Arc<[ArtifactKind]>,
+    honor_ignores: bool,
+    progress: Arc<Progress>,
+}
+
+/// Live progress counters, printed to stderr as work happens. A no-op when
+/// disabled, so default output (and `--dry-run`/scripted use) stays quiet.
+struct Progress {
+    enabled: bool,
+    count: AtomicUsize,
+    bytes: AtomicU64,
+}
+impl Progress {
+    fn new(enabled: bool) -> Arc<Self> {
+        Arc::new(Self {
+            enabled,
+            count: AtomicUsize::new(0),
+            bytes: AtomicU64::new(0),
+        })
+    }
+    /// Record one more artifact directory discovered during scanning
+    fn scan_found(&self, size: u64) {
+        if !self.enabled {
+            return;
+        }
+        let count = self.count.fetch_add(1, Ordering::Relaxed) + 1;
+        let bytes = self.bytes.fetch_add(size, Ordering::Relaxed) + size;
+        eprint!(
+            "\rScanning... {count} director{} found, {} so far",
+            if count == 1 { "y" } else { "ies" },
+            bytefmt::format(bytes)
+        );
+        let _ = stderr().flush();
+    }
+    /// Called once the scan has finished, to move off the in-place progress line
+    fn scan_done(&self) {
+        if self.enabled {
+            eprintln!();
+        }
+    }
+    /// Record one more directory removed during cleanup
+    fn clean_removed(&self, path: &Path, freed: u64) {
+        if !self.enabled {
+            return;
+        }
+        let total = self.bytes.fetch_add(freed, Ordering::Relaxed) + freed;
+        eprintln!(
+            "Removed {} ({} freed, {} total)",
+            path.display(),
+            bytefmt::format(freed),
+            bytefmt::format(total)
+        );
+    }
+}
+
+/// The `.gitignore`/`.ignore` matchers accumulated while descending into a tree,
+/// innermost directory last. A path is ignored if any ancestor's matcher says so.
+#[derive(Clone, Default)]
+struct IgnoreChain {
+    matchers: Arc<Vec<Gitignore>>,
+}
+impl IgnoreChain {
+    /// Add one matcher built from the given glob patterns, anchored at `base`
+    /// (the scan root) rather than the process's cwd, applied regardless of
+    /// directory (used for `--exclude`, which is honored even with `--no-ignore`)
+    fn push_excludes(&self, base: &Path, excludes: &[String]) -> Self {
+        let mut builder = GitignoreBuilder::new(base);
+        for pattern in excludes {
+            let _ = builder.add_line(None, pattern);
+        }
+        match builder.build() {
+            Ok(gi) => {
+                let mut matchers = (*self.matchers).clone();
+                matchers.push(gi);
+                IgnoreChain {
+                    matchers: Arc::new(matchers),
+                }
+            }
+            Err(_) => self.clone(),
+        }
+    }
+    /// Add a matcher for `dir`'s own `.gitignore`/`.ignore` files, if any exist
+    fn extend_with_dir(&self, dir: &Path) -> Self {
+        let mut builder = GitignoreBuilder::new(dir);
+        let mut found = false;
+        for name in [".gitignore", ".ignore"] {
+            let candidate = dir.join(name);
+            if candidate.exists() && builder.add(candidate).is_none() {
+                found = true;
+            }
+        }
+        if !found {
+            return self.clone();
+        }
+        match builder.build() {
+            Ok(gi) => {
+                let mut matchers = (*self.matchers).clone();
+                matchers.push(gi);
+                IgnoreChain {
+                    matchers: Arc::new(matchers),
+                }
+            }
+            Err(_) => self.clone(),
+        }
+    }
+    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        self.matchers
+            .iter()
+            .any(|m| m.matched(path, is_dir).is_ignore())
+    }
+}
+
+/// How the selected project directories should be disposed of
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum DisposalStrategy {
+    /// Move to the OS trash/recycle bin, recoverable
+    Trash,
+    /// Permanently delete from disk
+    Permanent,
 }
 
 static TARGET_PATH_STORE: OnceCell<Actor<PathInfoStore>> = OnceCell::const_new();
@@ -56,11 +298,42 @@ async fn main() -> Result<()> {
         "not found root path {}",
         scan_root.to_string_lossy()
     );
+    // Canonicalize once and walk the canonical path throughout, so the exclude
+    // matcher's base (anchored to this same path) actually lines up with the
+    // candidate paths `iter_path` builds while descending.
+    let scan_root = scan_root.canonicalize().unwrap_or_else(|_| scan_root.clone());
+    let disposal = if args.trash {
+        DisposalStrategy::Trash
+    } else {
+        DisposalStrategy::Permanent
+    };
+    let preserve_root = scan_root.clone();
+    let kinds: Arc<[ArtifactKind]> = if args.kinds.is_empty() {
+        Arc::from(ArtifactKind::ALL)
+    } else {
+        Arc::from(args.kinds.as_slice())
+    };
+    let scan_progress = Progress::new(args.progress);
+    let config = Arc::new(ScanConfig {
+        kinds,
+        honor_ignores: !args.no_ignore,
+        progress: scan_progress.clone(),
+    });
+    let mut ignores = IgnoreChain::default();
+    if !args.exclude.is_empty() {
+        ignores = ignores.push_excludes(&preserve_root, &args.exclude);
+    }
+    if config.honor_ignores {
+        ignores = ignores.extend_with_dir(&scan_root);
+    }
     let store = TARGET_PATH_STORE
-        .get_or_init(|| async move { PathInfoStore::new(args.keep_days, args.keep_size) })
+        .get_or_init(|| async move {
+            PathInfoStore::new(args.keep_days, args.keep_size, preserve_root)
+        })
         .await;
-    tokio::spawn(iter_path(scan_root)).await??;
-    store.display().await?;
+    tokio::spawn(iter_path(scan_root, config, ignores)).await??;
+    scan_progress.scan_done();
+    store.display(disposal, args.sort, args.reverse).await?;
     if args.dry_run {
         println!("Dry run. Not doing any cleanup");
         return Ok(());
@@ -78,54 +351,90 @@ async fn main() -> Result<()> {
         }
     }
     println!("Starting cleanup...");
-    store.clean().await?;
+    let clean_progress = Progress::new(args.progress);
+    let report = store.clean(disposal, args.force, clean_progress).await?;
+    println!(
+        "Freed {} across {} directories",
+        bytefmt::format(report.freed_bytes),
+        report.removed
+    );
+    if !report.failures.is_empty() {
+        println!("Failed to remove {} directories:", report.failures.len());
+        for (path, err) in &report.failures {
+            println!("  {}: {}", path.display(), err);
+        }
+    }
     println!("Done!");
     Ok(())
 }
 
+/// Find the `ArtifactRule` (among the enabled `kinds`) that `filename` is a marker
+/// for, given the directory it sits in as `parent`. Shared by `iter_path`'s own
+/// match check and by its recursion filter, so a directory that *is* a marker is
+/// never ignore-pruned before it gets the chance to be recognized as one — an
+/// artifact dir is exactly the kind of thing a project's own `.gitignore` lists.
+fn matching_rule(
+    kinds: &[ArtifactKind],
+    filename: &str,
+    parent: &Path,
+) -> Option<&'static ArtifactRule> {
+    ARTIFACT_RULES.iter().find(|rule| {
+        kinds.contains(&rule.kind)
+            && rule.marker_dir == filename
+            && (rule.sibling_manifests.is_empty()
+                || rule
+                    .sibling_manifests
+                    .iter()
+                    .any(|manifest| parent.join(manifest).exists()))
+    })
+}
+
 /// Iteration path all members
 #[async_recursion::async_recursion]
-async fn iter_path(path: PathBuf) -> Result<()> {
+async fn iter_path(path: PathBuf, config: Arc<ScanConfig>, ignores: IgnoreChain) -> Result<()> {
     if let Some(filename) = path.file_name() {
-        match filename.to_string_lossy().as_ref() {
-            ".git" => return Ok(()),
-            "target" => {
-                let cargo_path = {
-                    if let Some(parent) = path.parent() {
-                        let mut path = parent.to_path_buf();
-                        path.push("Cargo.toml");
-                        path
-                    } else {
-                        return Ok(());
-                    }
-                };
-
-                if cargo_path.exists() {
-                    let last_modified = path.metadata()?.modified()?;
-                    let size = iter_file_size(path.clone()).await?;
-                    return TARGET_PATH_STORE
-                        .get()
-                        .unwrap()
-                        .add_path_info(PathInfo {
-                            path,
-                            last_modified,
-                            size,
-                        })
-                        .await;
-                }
-            }
-            _ => {}
+        let filename = filename.to_string_lossy();
+        if filename == ".git" {
+            return Ok(());
+        }
+        let rule = path
+            .parent()
+            .and_then(|parent| matching_rule(&config.kinds, &filename, parent));
+        if let Some(rule) = rule {
+            let last_modified = path.metadata()?.modified()?;
+            let size = iter_file_size(path.clone()).await?;
+            config.progress.scan_found(size);
+            return TARGET_PATH_STORE
+                .get()
+                .unwrap()
+                .add_path_info(PathInfo {
+                    path,
+                    last_modified,
+                    size,
+                    kind: rule.kind,
+                })
+                .await;
         }
     }
     let dirs = match path.read_dir() {
         Ok(dir) => dir,
         Err(_) => return Ok(()),
     };
+    let ignores = if config.honor_ignores {
+        ignores.extend_with_dir(&path)
+    } else {
+        ignores
+    };
     for join in dirs
         .into_iter()
         .filter_map(|x| x.ok())
         .filter(|x| x.file_type().is_ok() && x.file_type().unwrap().is_dir())
-        .map(|x| tokio::spawn(iter_path(x.path())))
+        .filter(|x| {
+            let is_marker = matching_rule(&config.kinds, &x.file_name().to_string_lossy(), &path)
+                .is_some();
+            is_marker || !ignores.is_ignored(&x.path(), true)
+        })
+        .map(|x| tokio::spawn(iter_path(x.path(), config.clone(), ignores.clone())))
         .collect::<Vec<_>>()
     {
         join.await??;
@@ -163,46 +472,103 @@ struct PathInfo {
     path: PathBuf,
     last_modified: SystemTime,
     size: u64,
+    kind: ArtifactKind,
 }
-impl Display for PathInfo {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if let Some(Some(filename)) = self.path.parent().map(|x| x.file_name()) {
-            write!(
-                f,
-                "  {} : {}\n      {}, {}",
-                filename.to_str().unwrap_or_default(),
-                self.path.display(),
-                chrono::DateTime::<chrono::Local>::from(self.last_modified)
-                    .format("%Y-%m-%d %H:%M"),
-                bytefmt::format(self.size)
-            )
-        } else {
-            write!(
-                f,
-                " {}      {}    {}",
-                self.path.display(),
-                chrono::DateTime::<chrono::Local>::from(self.last_modified)
-                    .format("%Y-%m-%d %H:%M"),
-                bytefmt::format(self.size)
-            )
+impl PathInfo {
+    /// The project name shown in the table: the parent directory's name, falling
+    /// back to the full path when there is no parent (e.g. a root-level match)
+    fn project_name(&self) -> String {
+        match self.path.parent().and_then(|p| p.file_name()) {
+            Some(name) => name.to_string_lossy().into_owned(),
+            None => self.path.display().to_string(),
+        }
+    }
+}
+
+/// How the printed project lists should be ordered
+#[derive(Copy, Clone, Eq, PartialEq, Debug, ValueEnum)]
+enum SortMode {
+    Size,
+    Date,
+    Name,
+}
+impl SortMode {
+    /// Whether this mode's "natural" order is descending (size: biggest first,
+    /// date: most recent first) or ascending (name: alphabetical)
+    fn natural_descending(&self) -> bool {
+        matches!(self, SortMode::Size | SortMode::Date)
+    }
+}
+
+/// A single row of the aligned project table
+struct Row {
+    name: String,
+    path: String,
+    modified: String,
+    size: String,
+}
+impl Row {
+    fn from(info: &PathInfo) -> Self {
+        Row {
+            name: info.project_name(),
+            path: info.path.display().to_string(),
+            modified: chrono::DateTime::<chrono::Local>::from(info.last_modified)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+            size: bytefmt::format(info.size),
         }
     }
 }
 
+/// Print an aligned, columnar table of name / path / last-modified / size
+fn print_table(rows: &[Row]) {
+    let name_w = rows.iter().map(|r| r.name.len()).max().unwrap_or(0);
+    let path_w = rows.iter().map(|r| r.path.len()).max().unwrap_or(0);
+    let modified_w = rows.iter().map(|r| r.modified.len()).max().unwrap_or(0);
+    let size_w = rows.iter().map(|r| r.size.len()).max().unwrap_or(0);
+    for row in rows {
+        println!(
+            "  {:name_w$}  {:path_w$}  {:modified_w$}  {:>size_w$}",
+            row.name,
+            row.path,
+            row.modified,
+            row.size,
+            name_w = name_w,
+            path_w = path_w,
+            modified_w = modified_w,
+            size_w = size_w
+        );
+    }
+}
+
+/// Outcome of a full cleanup pass across all selected projects
+#[derive(Debug, Default)]
+struct CleanReport {
+    /// Number of directories actually removed
+    removed: usize,
+    /// Total bytes freed by the directories that were removed
+    freed_bytes: u64,
+    /// Directories that failed to be removed, paired with their error
+    failures: Vec<(PathBuf, anyhow::Error)>,
+}
+
 /// store all target path info and helper analyze
 struct PathInfoStore {
     keep_days: u32,
     keep_size: u64,
     projects: Vec<PathInfo>,
     ignores: Vec<PathInfo>,
+    /// Canonicalized scan root; removal of this path or any of its ancestors is refused
+    preserve_root: PathBuf,
 }
 impl PathInfoStore {
-    pub fn new(keep_days: u32, keep_size: u64) -> Actor<Self> {
+    pub fn new(keep_days: u32, keep_size: u64, preserve_root: PathBuf) -> Actor<Self> {
         Actor::new(Self {
             keep_size: keep_size * 1024 * 1024, //to MB
             keep_days,
             projects: vec![],
             ignores: vec![],
+            preserve_root,
         })
     }
     /// push target path info
@@ -218,18 +584,14 @@ impl PathInfoStore {
         }
     }
     /// display all target info
-    fn display(&self) {
+    fn display(&self, disposal: DisposalStrategy, sort: SortMode, reverse: bool) {
         if !self.ignores.is_empty() {
             println!("Ignoring the following project directories:");
-            for ignore in self.ignores.iter() {
-                println!("{}", ignore);
-            }
+            Self::print_grouped_by_kind(&self.ignores, sort, reverse);
         }
         if !self.projects.is_empty() {
             println!("Selected the following project directories for cleaning:");
-            for project in self.projects.iter() {
-                println!("{}", project);
-            }
+            Self::print_grouped_by_kind(&self.projects, sort, reverse);
         }
         let total_size: u64 = self.projects.iter().map(|it| it.size).sum();
         println!(
@@ -238,23 +600,143 @@ impl PathInfoStore {
             self.projects.len() + self.ignores.len(),
             bytefmt::format(total_size)
         );
+        match disposal {
+            DisposalStrategy::Trash => {
+                println!("Selected directories will be moved to the trash/recycle bin")
+            }
+            DisposalStrategy::Permanent => {
+                println!("Selected directories will be permanently removed")
+            }
+        }
+    }
+    /// Print a list of projects grouped by artifact kind, each group sorted and
+    /// rendered as an aligned table, with a per-kind subtotal
+    fn print_grouped_by_kind(infos: &[PathInfo], sort: SortMode, reverse: bool) {
+        let mut sorted: Vec<&PathInfo> = infos.iter().collect();
+        let descending = sort.natural_descending() != reverse;
+        sorted.sort_by(|a, b| {
+            let ord = match sort {
+                SortMode::Size => a.size.cmp(&b.size),
+                SortMode::Date => a.last_modified.cmp(&b.last_modified),
+                SortMode::Name => a.project_name().cmp(&b.project_name()),
+            };
+            if descending {
+                ord.reverse()
+            } else {
+                ord
+            }
+        });
+        let mut by_kind: BTreeMap<ArtifactKind, Vec<&PathInfo>> = BTreeMap::new();
+        for info in sorted {
+            by_kind.entry(info.kind).or_default().push(info);
+        }
+        for (kind, group) in by_kind {
+            let subtotal: u64 = group.iter().map(|it| it.size).sum();
+            println!(
+                "  -- {} ({} project(s), {}) --",
+                kind.label(),
+                group.len(),
+                bytefmt::format(subtotal)
+            );
+            let rows: Vec<Row> = group.iter().map(|info| Row::from(info)).collect();
+            print_table(&rows);
+        }
     }
-    ///clean all target
-    fn clean(&self) -> Result<()> {
-        for p in self.projects.iter() {
-            remove_dir_all::remove_dir_all(&p.path)?;
+    /// Concurrently remove every selected project directory, bounded to roughly the
+    /// CPU count at a time. Individual failures are collected rather than aborting
+    /// the batch when `force` is set; otherwise, removals are observed as they
+    /// complete and no further ones are scheduled once the first failure is seen
+    /// (already in-flight removals, bounded by the concurrency cap, still finish).
+    async fn clean(
+        &self,
+        disposal: DisposalStrategy,
+        force: bool,
+        progress: Arc<Progress>,
+    ) -> Result<CleanReport> {
+        let concurrency = num_cpus::get().max(1);
+        let preserve_root = self.preserve_root.clone();
+        let mut remaining = self.projects.clone().into_iter();
+        let mut pending = tokio::task::JoinSet::new();
+        let mut report = CleanReport::default();
+
+        for _ in 0..concurrency {
+            if !spawn_next_removal(&mut pending, &mut remaining, &preserve_root, disposal) {
+                break;
+            }
+        }
+
+        while let Some(joined) = pending.join_next().await {
+            let (info, result) = joined?;
+            match result {
+                Ok(()) => {
+                    report.removed += 1;
+                    report.freed_bytes += info.size;
+                    progress.clean_removed(&info.path, info.size);
+                    spawn_next_removal(&mut pending, &mut remaining, &preserve_root, disposal);
+                }
+                Err(err) if force => {
+                    report.failures.push((info.path, err));
+                    spawn_next_removal(&mut pending, &mut remaining, &preserve_root, disposal);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Pull the next project off `remaining`, if any, and spawn its removal as a
+/// blocking task in `pending`. Returns whether a task was spawned.
+fn spawn_next_removal(
+    pending: &mut tokio::task::JoinSet<(PathInfo, Result<()>)>,
+    remaining: &mut std::vec::IntoIter<PathInfo>,
+    preserve_root: &Path,
+    disposal: DisposalStrategy,
+) -> bool {
+    match remaining.next() {
+        Some(info) => {
+            let preserve_root = preserve_root.to_path_buf();
+            pending.spawn_blocking(move || {
+                let result = remove_one(&info.path, &preserve_root, disposal);
+                (info, result)
+            });
+            true
         }
-        Ok(())
+        None => false,
     }
 }
+
+/// Remove a single project directory, refusing to touch the scan root (or an ancestor
+/// of it), and unlinking rather than following any symlinked entry.
+fn remove_one(path: &Path, preserve_root: &Path, disposal: DisposalStrategy) -> Result<()> {
+    let canonical = path.canonicalize()?;
+    anyhow::ensure!(
+        canonical != preserve_root && !preserve_root.starts_with(&canonical),
+        "refusing to remove {} because it is, or contains, the scan root",
+        path.display()
+    );
+    if path.symlink_metadata()?.file_type().is_symlink() {
+        return Ok(std::fs::remove_file(path)?);
+    }
+    match disposal {
+        DisposalStrategy::Trash => trash::delete(path)?,
+        DisposalStrategy::Permanent => remove_dir_all::remove_dir_all(path)?,
+    }
+    Ok(())
+}
 #[async_trait::async_trait]
 trait IPathInfoStore {
     /// push target path info
     async fn add_path_info(&self, info: PathInfo) -> Result<()>;
     /// display all target info
-    async fn display(&self) -> Result<()>;
+    async fn display(&self, disposal: DisposalStrategy, sort: SortMode, reverse: bool) -> Result<()>;
     /// clean all target
-    async fn clean(&self) -> Result<()>;
+    async fn clean(
+        &self,
+        disposal: DisposalStrategy,
+        force: bool,
+        progress: Arc<Progress>,
+    ) -> Result<CleanReport>;
 }
 
 #[async_trait::async_trait]
@@ -266,15 +748,203 @@ impl IPathInfoStore for Actor<PathInfoStore> {
         })
         .await
     }
-    async fn display(&self) -> Result<()> {
+    async fn display(&self, disposal: DisposalStrategy, sort: SortMode, reverse: bool) -> Result<()> {
         self.inner_call(|inner| async move {
-            inner.get().display();
+            inner.get().display(disposal, sort, reverse);
             Ok(())
         })
         .await
     }
-    async fn clean(&self) -> Result<()> {
-        self.inner_call(|inner| async move { inner.get().clean() })
+    async fn clean(
+        &self,
+        disposal: DisposalStrategy,
+        force: bool,
+        progress: Arc<Progress>,
+    ) -> Result<CleanReport> {
+        self.inner_call(|inner| async move { inner.get().clean(disposal, force, progress).await })
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh directory under the OS temp dir, unique per call, cleaned up on drop
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(label: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "cleans-test-{label}-{}-{}",
+                std::process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+            std::fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = remove_dir_all::remove_dir_all(&self.0);
+        }
+    }
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    fn path_info(path: PathBuf, size: u64) -> PathInfo {
+        PathInfo {
+            path,
+            last_modified: SystemTime::now(),
+            size,
+            kind: ArtifactKind::Cargo,
+        }
+    }
+
+    #[test]
+    fn remove_one_refuses_to_remove_the_preserve_root_itself() {
+        let root = TempDir::new("root-equal");
+        let canonical_root = root.0.canonicalize().unwrap();
+
+        let err = remove_one(&root.0, &canonical_root, DisposalStrategy::Permanent).unwrap_err();
+        assert!(err.to_string().contains("refusing to remove"));
+        assert!(root.0.exists());
+    }
+
+    #[test]
+    fn remove_one_refuses_to_remove_an_ancestor_of_the_preserve_root() {
+        let root = TempDir::new("root-ancestor");
+        let nested = root.0.join("nested");
+        std::fs::create_dir_all(&nested).unwrap();
+        let canonical_nested = nested.canonicalize().unwrap();
+
+        // root.0 is an ancestor of the (nested) preserve root, so removing it must be refused
+        let err = remove_one(&root.0, &canonical_nested, DisposalStrategy::Permanent).unwrap_err();
+        assert!(err.to_string().contains("refusing to remove"));
+        assert!(root.0.exists());
+    }
+
+    #[test]
+    fn remove_one_removes_paths_outside_the_preserve_root() {
+        let root = TempDir::new("preserve");
+        let victim = TempDir::new("victim");
+        let canonical_root = root.0.canonicalize().unwrap();
+
+        remove_one(&victim.0, &canonical_root, DisposalStrategy::Permanent).unwrap();
+        assert!(!victim.0.exists());
+    }
+
+    #[tokio::test]
+    async fn clean_without_force_stops_reporting_after_the_first_failure() {
+        let root = TempDir::new("clean-no-force");
+        let canonical_root = root.0.canonicalize().unwrap();
+        let victim = TempDir::new("clean-no-force-victim");
+        let missing = root.0.join("does-not-exist");
+
+        let store = PathInfoStore {
+            keep_days: 0,
+            keep_size: 0,
+            projects: vec![path_info(missing, 0), path_info(victim.0.clone(), 1)],
+            ignores: Vec::new(),
+            preserve_root: canonical_root,
+        };
+
+        let err = store
+            .clean(DisposalStrategy::Permanent, false, Progress::new(false))
+            .await
+            .unwrap_err();
+        assert!(err.is::<std::io::Error>() || err.to_string().contains("No such file"));
+    }
+
+    #[tokio::test]
+    async fn clean_with_force_collects_failures_but_still_removes_valid_entries() {
+        let root = TempDir::new("clean-force");
+        let canonical_root = root.0.canonicalize().unwrap();
+        let victim = TempDir::new("clean-force-victim");
+        let victim_path = victim.0.clone();
+        let missing = root.0.join("does-not-exist");
+
+        let store = PathInfoStore {
+            keep_days: 0,
+            keep_size: 0,
+            projects: vec![path_info(missing, 0), path_info(victim_path.clone(), 1)],
+            ignores: Vec::new(),
+            preserve_root: canonical_root,
+        };
+
+        let report = store
+            .clean(DisposalStrategy::Permanent, true, Progress::new(false))
+            .await
+            .unwrap();
+        assert_eq!(report.removed, 1);
+        assert_eq!(report.failures.len(), 1);
+        assert!(!victim_path.exists());
+    }
+
+    #[tokio::test]
+    async fn iter_path_recognizes_artifact_markers_even_when_gitignored() {
+        let root = TempDir::new("ignore-vs-marker");
+        std::fs::write(root.0.join(".gitignore"), "target/\n").unwrap();
+        std::fs::write(root.0.join("Cargo.toml"), "[package]\n").unwrap();
+        let target_dir = root.0.join("target");
+        std::fs::create_dir_all(&target_dir).unwrap();
+        std::fs::write(target_dir.join("artifact.bin"), b"hello").unwrap();
+
+        let store = PathInfoStore::new(0, 0, root.0.clone());
+        TARGET_PATH_STORE.set(store).ok();
+
+        let config = Arc::new(ScanConfig {
+            kinds: Arc::from(vec![ArtifactKind::Cargo]),
+            honor_ignores: true,
+            progress: Progress::new(false),
+        });
+
+        iter_path(root.0.clone(), config, IgnoreChain::default())
+            .await
+            .unwrap();
+
+        let found = TARGET_PATH_STORE
+            .get()
+            .unwrap()
+            .inner_call(|inner| async move { Ok(inner.get().projects.len()) })
+            .await
+            .unwrap();
+        assert_eq!(found, 1, "the .gitignore'd target/ dir should still be found");
+    }
+
+    #[test]
+    fn matching_rule_requires_a_corroborating_manifest_for_build_and_dist() {
+        let root = TempDir::new("build-no-manifest");
+        assert!(matching_rule(&[ArtifactKind::Build], "build", &root.0).is_none());
+        assert!(matching_rule(&[ArtifactKind::Dist], "dist", &root.0).is_none());
+
+        std::fs::write(root.0.join("package.json"), "{}").unwrap();
+        assert!(matching_rule(&[ArtifactKind::Build], "build", &root.0).is_some());
+        assert!(matching_rule(&[ArtifactKind::Dist], "dist", &root.0).is_some());
+    }
+
+    #[test]
+    fn exclude_globs_are_anchored_to_the_given_base_not_the_cwd() {
+        let root = TempDir::new("exclude-anchor");
+        let excluded = root.0.join("vendor");
+        std::fs::create_dir_all(&excluded).unwrap();
+
+        let ignores = IgnoreChain::default().push_excludes(&root.0, &["vendor".to_string()]);
+        assert!(ignores.is_ignored(&excluded, true));
+        assert!(!ignores.is_ignored(&root.0.join("src"), true));
+    }
+
+    #[test]
+    fn exclude_globs_match_anchored_and_multi_segment_patterns_against_the_same_base() {
+        // Anchored/multi-segment patterns only match when the candidate path is built
+        // from the same base the matcher was anchored to (the canonicalized scan root,
+        // consistently, not a mix of canonical and non-canonical paths).
+        let root = TempDir::new("exclude-anchor-nested");
+        let nested = root.0.join("sub").join("vendor");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let ignores = IgnoreChain::default().push_excludes(&root.0, &["/sub/vendor".to_string()]);
+        assert!(ignores.is_ignored(&nested, true));
+    }
+}